@@ -1,5 +1,7 @@
 //! Interrupt management.
 
+use core::sync::atomic::{AtomicU64, Ordering};
+
 use handler_table::HandlerTable;
 
 use crate::platform::irq::{dispatch_irq, MAX_IRQ_COUNT};
@@ -12,11 +14,42 @@ pub type IrqHandler = handler_table::Handler;
 
 static IRQ_HANDLER_TABLE: HandlerTable<MAX_IRQ_COUNT> = HandlerTable::new();
 
+const INIT_COUNT: AtomicU64 = AtomicU64::new(0);
+/// Per-IRQ dispatch counters, parallel to [`IRQ_HANDLER_TABLE`].
+static IRQ_COUNTS: [AtomicU64; MAX_IRQ_COUNT] = [INIT_COUNT; MAX_IRQ_COUNT];
+/// Count of IRQs that had no registered handler.
+static UNHANDLED_COUNT: AtomicU64 = AtomicU64::new(0);
+
+/// Returns the number of times `irq_num` has been dispatched, or `0` if
+/// `irq_num` is out of range.
+pub fn stats(irq_num: usize) -> u64 {
+    if irq_num >= MAX_IRQ_COUNT {
+        return 0;
+    }
+    IRQ_COUNTS[irq_num].load(Ordering::Relaxed)
+}
+
+/// Returns an iterator over the dispatch counters for every IRQ source up to
+/// [`MAX_IRQ_COUNT`].
+pub fn stats_iter() -> impl Iterator<Item = u64> + 'static {
+    IRQ_COUNTS.iter().map(|c| c.load(Ordering::Relaxed))
+}
+
+/// Returns the number of IRQs that were dispatched with no registered
+/// handler.
+pub fn unhandled_stats() -> u64 {
+    UNHANDLED_COUNT.load(Ordering::Relaxed)
+}
+
 /// Platform-independent IRQ dispatching.
 #[allow(dead_code)]
 pub(crate) fn dispatch_irq_common(irq_num: usize) {
     trace!("IRQ {}", irq_num);
+    if irq_num < MAX_IRQ_COUNT {
+        IRQ_COUNTS[irq_num].fetch_add(1, Ordering::Relaxed);
+    }
     if !IRQ_HANDLER_TABLE.handle(irq_num) {
+        UNHANDLED_COUNT.fetch_add(1, Ordering::Relaxed);
         warn!("Unhandled IRQ {}", irq_num);
     }
 }
@@ -1,5 +1,10 @@
 #![allow(unused_imports)]
 
+#[cfg(feature = "embassy")]
+mod embassy;
+#[cfg(feature = "monotonic")]
+pub mod mono;
+
 use aarch64_cpu::registers::{CNTFRQ_EL0, CNTPCT_EL0, CNTP_CTL_EL0, CNTP_TVAL_EL0};
 use bitflags::bitflags;
 use int_ratio::Ratio;
@@ -84,6 +89,13 @@ pub(crate) fn init_percpu() {
         // CNTP_CTL_EL0.write(CNTP_CTL_EL0::ENABLE::SET);
         // CNTP_TVAL_EL0.set(0);
         TIMER.lock().init(32);
+
+        // Only one handler can own `TIMER_IRQ_NUM`. When the `embassy` feature
+        // is enabled it becomes that owner, and drives alarm expiry off the
+        // same tick; otherwise the IRQ is just enabled, as before.
+        #[cfg(feature = "embassy")]
+        crate::irq::register_handler(crate::platform::irq::TIMER_IRQ_NUM, || embassy::on_timer_irq());
+        #[cfg(not(feature = "embassy"))]
         crate::platform::irq::set_enable(crate::platform::irq::TIMER_IRQ_NUM, true);
     }
 }
@@ -0,0 +1,122 @@
+//! `embassy-time-driver` implementation backed by the generic timer.
+
+use embassy_time_driver::{AlarmHandle, Driver};
+use kspin::SpinNoIrq;
+
+use super::{current_ticks, set_oneshot_timer, ticks_to_nanos};
+
+/// The number of alarms this driver can track concurrently.
+const ALARM_COUNT: usize = 4;
+
+/// Converts a hardware tick count into the driver's tick rate
+/// (`embassy_time_driver::TICK_HZ`).
+fn hw_ticks_to_driver_ticks(hw_ticks: u64) -> u64 {
+    ticks_to_nanos(hw_ticks) / (1_000_000_000 / embassy_time_driver::TICK_HZ)
+}
+
+/// Converts a driver tick count back into nanoseconds, for programming the
+/// one-shot hardware timer.
+fn driver_ticks_to_nanos(driver_ticks: u64) -> u64 {
+    driver_ticks * (1_000_000_000 / embassy_time_driver::TICK_HZ)
+}
+
+#[derive(Clone, Copy)]
+struct Alarm {
+    timestamp: u64,
+    callback: fn(*mut ()),
+    ctx: *mut (),
+}
+
+unsafe impl Send for Alarm {}
+
+struct HalTimeDriver {
+    alarms: SpinNoIrq<[Option<Alarm>; ALARM_COUNT]>,
+}
+
+impl HalTimeDriver {
+    const fn new() -> Self {
+        Self {
+            alarms: SpinNoIrq::new([None; ALARM_COUNT]),
+        }
+    }
+
+    /// Fires every alarm whose deadline has passed, then reprograms the
+    /// hardware timer for the next pending one (if any).
+    fn on_timer_irq(&self) {
+        let now = self.now();
+        let mut due = [None; ALARM_COUNT];
+        {
+            let mut alarms = self.alarms.lock();
+            for (slot, alarm) in alarms.iter_mut().enumerate() {
+                if let Some(a) = alarm {
+                    if a.timestamp <= now {
+                        due[slot] = alarm.take();
+                    }
+                }
+            }
+        }
+        for alarm in due.into_iter().flatten() {
+            (alarm.callback)(alarm.ctx);
+        }
+        self.reschedule();
+    }
+
+    fn reschedule(&self) {
+        let alarms = self.alarms.lock();
+        // Allocated alarms that were never set hold `u64::MAX` and are not pending.
+        let pending = alarms.iter().flatten().map(|a| a.timestamp).filter(|&t| t != u64::MAX);
+        if let Some(next) = pending.min() {
+            set_oneshot_timer(driver_ticks_to_nanos(next));
+        }
+    }
+}
+
+impl Driver for HalTimeDriver {
+    fn now(&self) -> u64 {
+        hw_ticks_to_driver_ticks(current_ticks())
+    }
+
+    unsafe fn allocate_alarm(&self) -> Option<AlarmHandle> {
+        let mut alarms = self.alarms.lock();
+        let slot = alarms.iter().position(|a| a.is_none())?;
+        alarms[slot] = Some(Alarm {
+            timestamp: u64::MAX,
+            callback: |_| {},
+            ctx: core::ptr::null_mut(),
+        });
+        Some(AlarmHandle::new(slot as u8))
+    }
+
+    fn set_alarm_callback(&self, alarm: AlarmHandle, callback: fn(*mut ()), ctx: *mut ()) {
+        let mut alarms = self.alarms.lock();
+        if let Some(a) = &mut alarms[alarm.id() as usize] {
+            a.callback = callback;
+            a.ctx = ctx;
+        }
+    }
+
+    fn set_alarm(&self, alarm: AlarmHandle, timestamp: u64) -> bool {
+        if timestamp <= self.now() {
+            return false;
+        }
+        {
+            let mut alarms = self.alarms.lock();
+            if let Some(a) = &mut alarms[alarm.id() as usize] {
+                a.timestamp = timestamp;
+            }
+        }
+        self.reschedule();
+        true
+    }
+}
+
+embassy_time_driver::time_driver_impl!(static DRIVER: HalTimeDriver = HalTimeDriver::new());
+
+/// Checks for elapsed alarms and reprograms the hardware timer.
+///
+/// Called from the single handler registered for `TIMER_IRQ_NUM` by
+/// [`super::init_percpu`]; this driver does not register its own handler so
+/// it doesn't compete for that slot.
+pub(crate) fn on_timer_irq() {
+    DRIVER.on_timer_irq();
+}
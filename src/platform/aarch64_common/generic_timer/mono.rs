@@ -0,0 +1,66 @@
+//! An RTIC-style `Monotonic` wrapper around the generic timer, using
+//! absolute (`CNTP_CVAL`) rather than relative (`CNTP_TVAL`) comparison.
+
+use aarch64_cpu::registers::{CNTP_CTL_EL0, CNTP_CVAL_EL0};
+use tock_registers::interfaces::{Readable, Writeable};
+
+use super::{current_ticks, ticks_to_nanos, TimerCtrlFlags};
+
+/// A 64-bit, wrapping-free instant derived from the generic timer's counter.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Instant(u64);
+
+impl Instant {
+    /// Returns the number of nanoseconds elapsed since this HAL was started.
+    pub fn as_nanos(&self) -> u64 {
+        ticks_to_nanos(self.0)
+    }
+
+    /// Returns the raw hardware tick count.
+    pub fn as_ticks(&self) -> u64 {
+        self.0
+    }
+}
+
+/// A monotonic clock source suitable for cooperative schedulers, backed by
+/// the aarch64 generic timer's physical counter (`CNTPCT_EL0`).
+pub struct Monotonic;
+
+impl Monotonic {
+    /// Creates a new `Monotonic` clock. The underlying counter is already
+    /// free-running once the CPU is up, so there is nothing to start.
+    pub const fn new() -> Self {
+        Self
+    }
+
+    /// Returns the current instant.
+    pub fn now(&mut self) -> Instant {
+        Instant(current_ticks())
+    }
+
+    /// Programs the timer to fire when the counter reaches `instant`.
+    pub fn set_compare(&mut self, instant: Instant) {
+        CNTP_CVAL_EL0.set(instant.as_ticks());
+        let mut ctrl = TimerCtrlFlags::from_bits_truncate(CNTP_CTL_EL0.get());
+        ctrl.insert(TimerCtrlFlags::ENABLE);
+        ctrl.remove(TimerCtrlFlags::IMASK);
+        CNTP_CTL_EL0.set(ctrl.bits());
+    }
+
+    /// Clears the timer's pending/compare-match flag by masking interrupts
+    /// until the next `set_compare`.
+    pub fn clear_compare_flag(&mut self) {
+        let mut ctrl = TimerCtrlFlags::from_bits_truncate(CNTP_CTL_EL0.get());
+        ctrl.insert(TimerCtrlFlags::IMASK);
+        CNTP_CTL_EL0.set(ctrl.bits());
+    }
+
+    /// Returns the current instant, for callers to use as their "zero" time.
+    ///
+    /// The hardware counter itself cannot be rewound and `Monotonic` keeps no
+    /// state, so nothing is reset; callers track elapsed time relative to the
+    /// returned instant.
+    pub fn zero(&mut self) -> Instant {
+        self.now()
+    }
+}
@@ -58,26 +58,25 @@ pub(crate) fn init_secondary() {
     GICC.init();
 }
 
-/// 发送yield中断信号
-pub fn send_ipi(_vector: u8, _dest: u32) {
-    use aarch64_cpu::registers::Readable;
-    let intid = 3;
-    let mpidr = aarch64_cpu::registers::MPIDR_EL1.get();
-    let cpu_id = mpidr >> 8 & 0xff;
-    let value = 1 << (cpu_id + 16) | intid;
+/// Offset of `GICD_SGIR` (Software Generated Interrupt Register) from the
+/// distributor base.
+const GICD_SGIR: usize = 0xf00;
+
+/// Sends a Software Generated Interrupt to the CPUs selected by `dest` (a
+/// bitmask, one bit per target CPU interface), using `vector` as the SGI
+/// interrupt id.
+pub fn send_ipi(vector: u8, dest: u32) {
+    let value = ((dest & 0xff) << 16) | (vector as u32 & 0xf);
     unsafe {
         core::ptr::write_volatile(
-            // 0xff84_1000 + 0xFFFFFF8000000000 + 0x0f00
-            18446743528240586496 as *mut u32,
-            value as _,
+            (phys_to_virt(GICD_BASE).as_usize() + GICD_SGIR) as *mut u32,
+            value,
         )
     };
 }
 
 pub fn end_of_interrupt(irq: usize) {
-    let gicc_base: usize = 0xff84_1000 + 0xFFFFFF8000000000;
-
     unsafe {
-        core::ptr::write_volatile((gicc_base + 0x0010) as *mut u32, irq as _);
+        core::ptr::write_volatile((phys_to_virt(GICC_BASE).as_usize() + 0x0010) as *mut u32, irq as _);
     }
 }
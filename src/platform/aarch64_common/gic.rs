@@ -51,21 +51,23 @@ pub(crate) fn init_primary() {
     gic_v3.inner.enable_interrupt(IntId::sgi(3), true);
 }
 
-/// 发送yield中断信号
-pub fn send_ipi(_vector: u8, _dest: u32) {
+/// Sends a Software Generated Interrupt to the CPUs selected by `dest` (a
+/// bitmask of affinity-0 CPU ids within the current cluster), using `vector`
+/// as the SGI interrupt id.
+pub fn send_ipi(vector: u8, dest: u32) {
     use aarch64_cpu::registers::Readable;
     let mpidr = aarch64_cpu::registers::MPIDR_EL1.get();
     let aff1 = mpidr >> 8 & 0xff;
     let aff2 = mpidr >> 16 & 0xff;
     let aff3 = mpidr >> 32 & 0xff;
-    let sgi_intid = IntId::sgi(3);
+    let sgi_intid = IntId::sgi(vector as _);
     GicV3::send_sgi(
         sgi_intid,
         arm_gic::gicv3::SgiTarget::List {
             affinity3: aff3 as _,
             affinity2: aff2 as _,
             affinity1: aff1 as _,
-            target_list: 0b1,
+            target_list: dest as _,
         },
     );
 }
@@ -78,10 +80,25 @@ pub fn get_and_acknowledge_interrupt() -> usize {
     u32::from(GicV3::get_and_acknowledge_interrupt().unwrap()) as _
 }
 
+/// Dispatches the IRQ.
+///
+/// This function is called by the common interrupt handler. It looks
+/// up in the IRQ handler table and calls the corresponding handler. If
+/// necessary, it also acknowledges the interrupt controller after handling.
 pub fn dispatch_irq(_unused: usize) {
-    unimplemented!()
+    let Some(intid) = GicV3::get_and_acknowledge_interrupt() else {
+        // Spurious interrupt (INTID 1023): nothing is actually pending.
+        return;
+    };
+    let irq_num = u32::from(intid) as usize;
+    crate::irq::dispatch_irq_common(irq_num);
+    end_of_interrupt(irq_num);
 }
 
+/// Registers an IRQ handler for the given IRQ.
+///
+/// It also enables the IRQ if the registration succeeds. It returns `false` if
+/// the registration failed.
 pub fn register_handler(irq_num: usize, handler: crate::irq::IrqHandler) -> bool {
-    unimplemented!()
+    crate::irq::register_handler_common(irq_num, handler)
 }
@@ -0,0 +1,114 @@
+//! Interrupt-driven UART receive support, built on top of the PL011 used by
+//! the common aarch64 platforms.
+//!
+//! TX remains blocking; only RX is moved off the polling path and onto
+//! [`UART_IRQ_NUM`](crate::platform::irq::UART_IRQ_NUM).
+
+use kspin::SpinNoIrq;
+use memory_addr::PhysAddr;
+
+use crate::mem::phys_to_virt;
+use crate::platform::irq::UART_IRQ_NUM;
+
+const UART_BASE: PhysAddr = pa!(axconfig::UART_PADDR);
+
+/// PL011 register offsets used here.
+const DR: usize = 0x00;
+const FR: usize = 0x18;
+const IMSC: usize = 0x38;
+const ICR: usize = 0x44;
+
+/// `FR.RXFE`: the receive FIFO is empty.
+const FR_RXFE: u32 = 1 << 4;
+/// `IMSC.RXIM`: unmask the receive interrupt.
+const IMSC_RXIM: u32 = 1 << 4;
+/// `ICR` write-1-to-clear mask covering all interrupt sources.
+const ICR_ALL: u32 = 0x7ff;
+
+/// Size of the software RX ring buffer, which holds received bytes until they
+/// are read. Once it is full, the oldest byte is dropped for each new one.
+const RX_BUF_LEN: usize = 256;
+
+struct RxRingBuffer {
+    buf: [u8; RX_BUF_LEN],
+    head: usize,
+    tail: usize,
+    len: usize,
+}
+
+impl RxRingBuffer {
+    const fn new() -> Self {
+        Self {
+            buf: [0; RX_BUF_LEN],
+            head: 0,
+            tail: 0,
+            len: 0,
+        }
+    }
+
+    fn push(&mut self, byte: u8) {
+        if self.len == RX_BUF_LEN {
+            // Drop the oldest byte rather than blocking in interrupt context.
+            self.tail = (self.tail + 1) % RX_BUF_LEN;
+            self.len -= 1;
+        }
+        self.buf[self.head] = byte;
+        self.head = (self.head + 1) % RX_BUF_LEN;
+        self.len += 1;
+    }
+
+    fn pop(&mut self) -> Option<u8> {
+        if self.len == 0 {
+            return None;
+        }
+        let byte = self.buf[self.tail];
+        self.tail = (self.tail + 1) % RX_BUF_LEN;
+        self.len -= 1;
+        Some(byte)
+    }
+}
+
+static RX_BUFFER: SpinNoIrq<RxRingBuffer> = SpinNoIrq::new(RxRingBuffer::new());
+
+fn reg(offset: usize) -> *mut u32 {
+    (phys_to_virt(UART_BASE).as_usize() + offset) as *mut u32
+}
+
+fn rx_fifo_empty() -> bool {
+    unsafe { core::ptr::read_volatile(reg(FR)) & FR_RXFE != 0 }
+}
+
+/// Drains the hardware RX FIFO into the software ring buffer. Called from
+/// the UART IRQ handler.
+fn drain_fifo() {
+    let mut rx = RX_BUFFER.lock();
+    while !rx_fifo_empty() {
+        rx.push(unsafe { core::ptr::read_volatile(reg(DR)) as u8 });
+    }
+    unsafe { core::ptr::write_volatile(reg(ICR), ICR_ALL) };
+}
+
+/// Non-blocking read of a single byte from the software RX buffer.
+///
+/// Returns `None` if the buffer is empty.
+pub fn try_read() -> Option<u8> {
+    RX_BUFFER.lock().pop()
+}
+
+/// Reads a single byte, busy-waiting on the ring buffer if it's empty.
+pub fn read_byte() -> u8 {
+    loop {
+        if let Some(byte) = try_read() {
+            return byte;
+        }
+        core::hint::spin_loop();
+    }
+}
+
+/// Registers the UART RX interrupt handler and unmasks `RXIM`.
+///
+/// Must be called once the interrupt controller is initialized.
+pub(crate) fn init_percpu() {
+    crate::irq::register_handler(UART_IRQ_NUM, drain_fifo);
+    unsafe { core::ptr::write_volatile(reg(IMSC), IMSC_RXIM) };
+}
@@ -1,4 +1,4 @@
-//! TODO: PLIC
+mod plic;
 
 use crate::irq::IrqHandler;
 use lazyinit::LazyInit;
@@ -8,7 +8,6 @@ use riscv::register::sie;
 pub(super) const INTC_IRQ_BASE: usize = 1 << (usize::BITS - 1);
 
 /// Supervisor software interrupt in `scause`
-#[allow(unused)]
 pub(super) const S_SOFT: usize = INTC_IRQ_BASE + 1;
 
 /// Supervisor timer interrupt in `scause`
@@ -33,13 +32,23 @@ macro_rules! with_cause {
             _ => panic!("invalid trap cause: {:#x}", $cause),
         }
     };
+    ($cause: expr, @TIMER => $timer_op: expr, @EXT => $ext_op: expr, @SOFT => $soft_op: expr $(,)?) => {
+        match $cause {
+            S_TIMER => $timer_op,
+            S_EXT => $ext_op,
+            S_SOFT => $soft_op,
+            _ => panic!("invalid trap cause: {:#x}", $cause),
+        }
+    };
 }
 
 /// Enables or disables the given IRQ.
-pub fn set_enable(scause: usize, _enabled: bool) {
-    if scause == S_EXT {
-        // TODO: set enable in PLIC
-    }
+///
+/// `irq_num` is a plain PLIC source id (the `@EXT` arm of [`register_handler`]
+/// strips the `scause` `Interrupt` bit before the id reaches this through
+/// [`crate::irq::register_handler_common`]).
+pub fn set_enable(irq_num: usize, enabled: bool) {
+    plic::set_enable(irq_num, enabled, cpu_id() as usize);
 }
 
 /// Registers an IRQ handler for the given IRQ.
@@ -71,7 +80,19 @@ pub fn dispatch_irq(scause: usize) {
             trace!("IRQ: timer");
             TIMER_HANDLER();
         },
-        @EXT => crate::irq::dispatch_irq_common(0), // TODO: get IRQ number from PLIC
+        @EXT => {
+            let irq_num = plic::claim(cpu_id() as usize);
+            if irq_num == 0 {
+                // Spurious interrupt: nothing is actually pending.
+                return;
+            }
+            crate::irq::dispatch_irq_common(irq_num);
+            end_of_interrupt(irq_num);
+        },
+        @SOFT => {
+            trace!("IRQ: ipi");
+            clear_ipi();
+        },
     );
 }
 
@@ -82,6 +103,7 @@ pub(super) fn init_percpu() {
         sie::set_stimer();
         sie::set_sext();
     }
+    plic::init_percpu(cpu_id() as usize);
 }
 
 fn cpu_id() -> u8 {
@@ -98,12 +120,19 @@ pub fn send_ipi(vector: u8, dest: u32) {
 }
 
 pub fn get_and_acknowledge_interrupt() -> usize {
-    //plic
-    unimplemented!()
+    plic::claim(cpu_id() as usize)
 }
 
+/// Completes handling of an external (PLIC-claimed) interrupt.
+///
+/// IPIs are acknowledged separately in the software-interrupt trap path, not
+/// here, so that completing a device IRQ can't clear an unrelated pending
+/// IPI on this hart.
 pub fn end_of_interrupt(irq: usize) {
-    //plic
-    // unimplemented!()
+    plic::complete(irq, cpu_id() as usize);
+}
+
+/// Acknowledges a received IPI (supervisor software interrupt) on this hart.
+pub fn clear_ipi() {
     sbi_rt::legacy::clear_ipi();
 }
@@ -0,0 +1,73 @@
+//! Minimal driver for the RISC-V Platform-Level Interrupt Controller (PLIC).
+
+use memory_addr::PhysAddr;
+
+use crate::mem::phys_to_virt;
+
+const PLIC_BASE: PhysAddr = pa!(axconfig::PLIC_PADDR);
+
+/// Priority register for source `irq` is a 32-bit word at `base + 4 * irq`.
+const PRIORITY_BASE: usize = 0x0;
+/// Per-context enable bit arrays, `0x80` bytes apart, starting at `base + 0x2000`.
+const ENABLE_BASE: usize = 0x2000;
+const ENABLE_STRIDE: usize = 0x80;
+/// Per-context priority-threshold and claim/complete registers, `0x1000` bytes
+/// apart, starting at `base + 0x20_0000`.
+const CONTEXT_BASE: usize = 0x20_0000;
+const CONTEXT_STRIDE: usize = 0x1000;
+const CONTEXT_THRESHOLD: usize = 0x0;
+const CONTEXT_CLAIM: usize = 0x4;
+
+/// Returns the supervisor-mode context index for the given hart.
+///
+/// By convention each hart has two contexts, M-mode and S-mode, so the S-mode
+/// context is `hart * 2 + 1`.
+fn s_context(hart_id: usize) -> usize {
+    hart_id * 2 + 1
+}
+
+fn reg(offset: usize) -> *mut u32 {
+    (phys_to_virt(PLIC_BASE).as_usize() + offset) as *mut u32
+}
+
+/// Sets the priority of `irq` to a nonzero value so it can fire, and enables
+/// or disables it for the S-mode context of the current hart.
+pub fn set_enable(irq: usize, enabled: bool, hart_id: usize) {
+    unsafe {
+        core::ptr::write_volatile(reg(PRIORITY_BASE + 4 * irq), 1);
+
+        let ctx = s_context(hart_id);
+        let enable_reg = reg(ENABLE_BASE + ctx * ENABLE_STRIDE + (irq / 32) * 4);
+        let mut bits = core::ptr::read_volatile(enable_reg);
+        if enabled {
+            bits |= 1 << (irq % 32);
+        } else {
+            bits &= !(1 << (irq % 32));
+        }
+        core::ptr::write_volatile(enable_reg, bits);
+    }
+}
+
+/// Sets the priority threshold for the S-mode context of the current hart,
+/// so that only sources with a higher priority are claimed.
+pub fn init_percpu(hart_id: usize) {
+    let ctx = s_context(hart_id);
+    unsafe {
+        core::ptr::write_volatile(reg(CONTEXT_BASE + ctx * CONTEXT_STRIDE + CONTEXT_THRESHOLD), 0);
+    }
+}
+
+/// Claims the highest-priority pending interrupt, returning its source id, or
+/// `0` if none is pending (spurious).
+pub fn claim(hart_id: usize) -> usize {
+    let ctx = s_context(hart_id);
+    unsafe { core::ptr::read_volatile(reg(CONTEXT_BASE + ctx * CONTEXT_STRIDE + CONTEXT_CLAIM)) as _ }
+}
+
+/// Signals completion of handling `irq`, allowing it to be claimed again.
+pub fn complete(irq: usize, hart_id: usize) {
+    let ctx = s_context(hart_id);
+    unsafe {
+        core::ptr::write_volatile(reg(CONTEXT_BASE + ctx * CONTEXT_STRIDE + CONTEXT_CLAIM), irq as _);
+    }
+}